@@ -0,0 +1,142 @@
+use std::io;
+
+use crate::Json;
+
+/// Writes `s` to `writer` as a quoted JSON string, escaping characters
+/// that are not allowed to appear literally.
+fn escape_str(writer: &mut dyn io::Write, s: &str) -> io::Result<()> {
+    writer.write_all(b"\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => writer.write_all(b"\\\"")?,
+            '\\' => writer.write_all(b"\\\\")?,
+            '\n' => writer.write_all(b"\\n")?,
+            '\r' => writer.write_all(b"\\r")?,
+            '\t' => writer.write_all(b"\\t")?,
+            '\u{8}' => writer.write_all(b"\\b")?,
+            '\u{c}' => writer.write_all(b"\\f")?,
+            c if (c as u32) < 0x20 => write!(writer, "\\u{:04x}", c as u32)?,
+            c => write!(writer, "{}", c)?,
+        }
+    }
+    writer.write_all(b"\"")
+}
+
+/// A compact JSON encoder: no whitespace between tokens.
+///
+/// Writes directly to any `&mut dyn io::Write`, so large documents can be
+/// streamed to a file or socket without building the whole output in
+/// memory first.
+pub struct Encoder<'a> {
+    writer: &'a mut dyn io::Write,
+}
+
+impl<'a> Encoder<'a> {
+    pub fn new(writer: &'a mut dyn io::Write) -> Self {
+        Encoder { writer }
+    }
+
+    pub fn encode(&mut self, json: &Json) -> io::Result<()> {
+        match json {
+            Json::Null => self.writer.write_all(b"null"),
+            Json::Boolean(b) => self.writer.write_all(if *b { b"true" } else { b"false" }),
+            Json::Number(n) => write!(self.writer, "{}", n),
+            Json::String(s) => escape_str(self.writer, s),
+            Json::Array(items) => {
+                self.writer.write_all(b"[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        self.writer.write_all(b",")?;
+                    }
+                    self.encode(item)?;
+                }
+                self.writer.write_all(b"]")
+            }
+            Json::Object(map) => {
+                self.writer.write_all(b"{")?;
+                for (i, (key, value)) in map.iter().enumerate() {
+                    if i > 0 {
+                        self.writer.write_all(b",")?;
+                    }
+                    escape_str(self.writer, key)?;
+                    self.writer.write_all(b":")?;
+                    self.encode(value)?;
+                }
+                self.writer.write_all(b"}")
+            }
+        }
+    }
+}
+
+/// A pretty-printing JSON encoder.
+///
+/// Like `Encoder`, but indents each nesting level by `indent` spaces and
+/// places each array/object entry on its own line, with no trailing
+/// comma.
+pub struct PrettyEncoder<'a> {
+    writer: &'a mut dyn io::Write,
+    indent: usize,
+    depth: usize,
+}
+
+impl<'a> PrettyEncoder<'a> {
+    pub fn new(writer: &'a mut dyn io::Write, indent: usize) -> Self {
+        PrettyEncoder {
+            writer,
+            indent,
+            depth: 0,
+        }
+    }
+
+    fn write_indent(&mut self) -> io::Result<()> {
+        write!(self.writer, "{}", " ".repeat(self.indent * self.depth))
+    }
+
+    pub fn encode(&mut self, json: &Json) -> io::Result<()> {
+        match json {
+            Json::Null => self.writer.write_all(b"null"),
+            Json::Boolean(b) => self.writer.write_all(if *b { b"true" } else { b"false" }),
+            Json::Number(n) => write!(self.writer, "{}", n),
+            Json::String(s) => escape_str(self.writer, s),
+            Json::Array(items) => {
+                if items.is_empty() {
+                    return self.writer.write_all(b"[]");
+                }
+                self.writer.write_all(b"[\n")?;
+                self.depth += 1;
+                for (i, item) in items.iter().enumerate() {
+                    self.write_indent()?;
+                    self.encode(item)?;
+                    if i + 1 != items.len() {
+                        self.writer.write_all(b",")?;
+                    }
+                    self.writer.write_all(b"\n")?;
+                }
+                self.depth -= 1;
+                self.write_indent()?;
+                self.writer.write_all(b"]")
+            }
+            Json::Object(map) => {
+                if map.is_empty() {
+                    return self.writer.write_all(b"{}");
+                }
+                self.writer.write_all(b"{\n")?;
+                self.depth += 1;
+                let len = map.len();
+                for (i, (key, value)) in map.iter().enumerate() {
+                    self.write_indent()?;
+                    escape_str(self.writer, key)?;
+                    self.writer.write_all(b": ")?;
+                    self.encode(value)?;
+                    if i + 1 != len {
+                        self.writer.write_all(b",")?;
+                    }
+                    self.writer.write_all(b"\n")?;
+                }
+                self.depth -= 1;
+                self.write_indent()?;
+                self.writer.write_all(b"}")
+            }
+        }
+    }
+}
@@ -1,90 +1,173 @@
-use std::collections::HashMap;
-use std::fmt::{Debug, Display};
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
 use std::fs::File;
-use std::io::prelude::*;
-use std::process;
+use std::io::{self, prelude::*};
 use std::str::FromStr;
 
-pub struct Json<V: FromStr + Debug + Display> {
-    data: HashMap<String, V>,
+mod encoder;
+mod parser;
+mod to_json;
+
+pub use encoder::{Encoder, PrettyEncoder};
+pub use parser::{ErrorCode, ParserError};
+pub use to_json::{FromJson, FromJsonError, ToJson};
+
+/// A JSON value.
+///
+/// Unlike a flat `HashMap<String, V>`, this models the full JSON value
+/// space: objects can nest arbitrarily, arrays can hold mixed types, and
+/// `Number`, `Boolean`, and `Null` are first-class variants.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Boolean(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(BTreeMap<String, Json>),
 }
 
-impl<V: FromStr + Debug + Display> Json<V> {
-    pub fn new() -> Self {
-        Self {
-            data: HashMap::new(),
-        }
+impl Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.encode(2))
     }
+}
 
-    pub fn encode(&self, indent: usize) -> String {
-        let mut result = String::new();
-        result.push_str("{\n");
-        for key in self.data.keys() {
-            let value = self.data.get(key).unwrap();
-            let indent_space = " ".repeat(indent);
-            result.push_str(
-                format!("{}\"{}\":{}{},\n", indent_space, key, indent_space, value).as_str(),
-            );
-        }
-     
-        result.push_str("}");
-        result
+impl Default for Json {
+    fn default() -> Self {
+        Self::new()
     }
+}
 
-    pub fn decode(&mut self, src: &mut File)
-    where
-        <V as FromStr>::Err: Debug + Display,
-    {
-        let mut contents = String::new();
-        src.read_to_string(&mut contents)
-            .expect("Error reading source file");
+impl FromStr for Json {
+    type Err = ParserError;
+
+    /// Parses a complete JSON value from `s`.
+    ///
+    /// The value need not be an object: any JSON value is accepted at
+    /// the top level. Leading/trailing whitespace is ignored, but any
+    /// other trailing content is rejected as `TrailingCharacters`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parser::from_str(s)
+    }
+}
 
-        if !contents.starts_with("{") && !contents.ends_with("}") {
-            process::exit(1);
+/// An error produced while decoding a JSON document from a byte stream:
+/// either the stream itself failed, or the bytes it produced weren't
+/// valid JSON.
+#[derive(Debug)]
+pub enum DecodeError {
+    Io(io::Error),
+    Parse(ParserError),
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Io(e) => write!(f, "{}", e),
+            DecodeError::Parse(e) => write!(f, "{}", e),
         }
+    }
+}
 
-        let parsed_contents = contents.replace("{", "").replace("}", "").replace("\n", "");
+impl std::error::Error for DecodeError {}
 
-        let lines: Vec<&str> = parsed_contents.split(",").collect();
+impl From<io::Error> for DecodeError {
+    fn from(e: io::Error) -> Self {
+        DecodeError::Io(e)
+    }
+}
 
-        for line in lines.into_iter() {
-            let line: Vec<&str> = line.trim().split(":").collect();
-            let key = line.get(0).unwrap().replace("\"", "");
-            let value = line
-                .get(1)
-                .expect("Value might be empty")
-                .trim()
-                .replace("\"", "");
+impl From<ParserError> for DecodeError {
+    fn from(e: ParserError) -> Self {
+        DecodeError::Parse(e)
+    }
+}
 
-            let parsed_value: V = match value.parse() {
-                Ok(v) => v,
-                Err(e) => panic!("{}", e),
-            };
+impl Json {
+    /// Creates an empty top-level JSON object.
+    pub fn new() -> Self {
+        Json::Object(BTreeMap::new())
+    }
 
-            self.data.insert(key, parsed_value);
+    /// Returns the keys of the top-level object, or an empty vec if this
+    /// value is not an object.
+    pub fn get_keys(&self) -> Vec<&String> {
+        match self {
+            Json::Object(map) => map.keys().collect(),
+            _ => Vec::new(),
         }
     }
 
-    pub fn get_keys(&self) -> Vec<&String> {
-        self.data.keys().collect::<Vec<&String>>()
+    /// Returns the values of the top-level object, or an empty vec if this
+    /// value is not an object.
+    pub fn get_values(&self) -> Vec<&Json> {
+        match self {
+            Json::Object(map) => map.values().collect(),
+            _ => Vec::new(),
+        }
     }
 
-    pub fn get_values(&self) -> Vec<&V> {
-        self.data.values().collect::<Vec<&V>>()
+    /// Inserts a key/value pair into the top-level object.
+    ///
+    /// Panics if this value is not an object.
+    pub fn insert(&mut self, key: String, value: Json) {
+        match self {
+            Json::Object(map) => {
+                map.insert(key, value);
+            }
+            _ => panic!("cannot insert into a non-object Json value"),
+        }
+    }
+
+    /// Encodes this value as a pretty-printed JSON string, indenting
+    /// nested levels by `indent` spaces.
+    pub fn encode(&self, indent: usize) -> String {
+        let mut buf = Vec::new();
+        self.to_pretty_writer(&mut buf, indent)
+            .expect("writing to a Vec<u8> cannot fail");
+        String::from_utf8(buf).expect("encoder only ever writes valid UTF-8")
+    }
+
+    /// Encodes this value as compact JSON (no whitespace) and writes it
+    /// directly to `writer`.
+    pub fn to_writer(&self, writer: &mut dyn io::Write) -> io::Result<()> {
+        Encoder::new(writer).encode(self)
+    }
+
+    /// Encodes this value as pretty-printed JSON, indenting nested levels
+    /// by `indent` spaces, and writes it directly to `writer`.
+    pub fn to_pretty_writer(&self, writer: &mut dyn io::Write, indent: usize) -> io::Result<()> {
+        PrettyEncoder::new(writer, indent).encode(self)
+    }
+
+    /// Reads `reader` to completion and parses a complete JSON value from
+    /// it, e.g. for parsing from stdin or a network stream.
+    pub fn from_reader<R: Read>(reader: &mut R) -> Result<Json, DecodeError> {
+        let mut contents = String::new();
+        reader.read_to_string(&mut contents)?;
+        Ok(contents.parse()?)
+    }
+
+    /// Parses a JSON document from `src`, replacing the contents of
+    /// `self` with the result.
+    pub fn decode(&mut self, src: &mut File) -> Result<(), DecodeError> {
+        *self = Json::from_reader(src)?;
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+
     #[test]
     fn test() {
-        let mut json_data: Json<i32> = Json::new();
+        let mut json_data = Json::new();
         let mut src = File::open("sample.json").unwrap();
 
-        json_data.decode(&mut src);
-        
+        json_data.decode(&mut src).unwrap();
+
         let encoded_data = json_data.encode(2);
         let mut actual = String::new();
         let mut f = File::open("sample.json").unwrap();
@@ -92,4 +175,31 @@ mod tests {
 
         assert_eq!(actual, encoded_data);
     }
+
+    #[test]
+    fn round_trips_quotes_and_backslashes() {
+        let original = Json::String("she said \"hi\\bye\"".to_string());
+        let encoded = original.encode(2);
+        assert_eq!(encoded, r#""she said \"hi\\bye\"""#);
+        assert_eq!(parser::from_str(&encoded).unwrap(), original);
+    }
+
+    #[test]
+    fn round_trips_control_characters() {
+        let original = Json::String("line\nbreak\ttab\r\x07bell".to_string());
+        let encoded = original.encode(2);
+        let decoded = parser::from_str(&encoded).unwrap();
+        assert_eq!(decoded, original);
+        // Re-encoding the decoded value should be byte-for-byte identical.
+        assert_eq!(decoded.encode(2), encoded);
+    }
+
+    #[test]
+    fn round_trips_surrogate_pair() {
+        // U+1F600 GRINNING FACE, encoded as the surrogate pair 😀.
+        let decoded = parser::from_str("\"\\ud83d\\ude00\"").unwrap();
+        assert_eq!(decoded, Json::String("\u{1F600}".to_string()));
+        assert_eq!(decoded.encode(2), "\"\u{1F600}\"");
+        assert_eq!(parser::from_str(&decoded.encode(2)).unwrap(), decoded);
+    }
 }
@@ -0,0 +1,485 @@
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use std::iter::Peekable;
+
+use crate::Json;
+
+/// The kind of problem a `Parser` ran into while reading a document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    InvalidSyntax,
+    EOFWhileParsingObject,
+    EOFWhileParsingArray,
+    EOFWhileParsingValue,
+    EOFWhileParsingString,
+    KeyMustBeAString,
+    ExpectedColon,
+    ExpectedListCommaOrEnd,
+    ExpectedObjectCommaOrEnd,
+    InvalidNumber,
+    InvalidEscape,
+    InvalidUnicodeCodePoint,
+    LoneLeadingSurrogateInHexEscape,
+    UnexpectedEndOfHexEscape,
+    TrailingCharacters,
+}
+
+impl Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            ErrorCode::InvalidSyntax => "invalid syntax",
+            ErrorCode::EOFWhileParsingObject => "EOF while parsing an object",
+            ErrorCode::EOFWhileParsingArray => "EOF while parsing an array",
+            ErrorCode::EOFWhileParsingValue => "EOF while parsing a value",
+            ErrorCode::EOFWhileParsingString => "EOF while parsing a string",
+            ErrorCode::KeyMustBeAString => "key must be a string",
+            ErrorCode::ExpectedColon => "expected `:`",
+            ErrorCode::ExpectedListCommaOrEnd => "expected `,` or `]`",
+            ErrorCode::ExpectedObjectCommaOrEnd => "expected `,` or `}`",
+            ErrorCode::InvalidNumber => "invalid number",
+            ErrorCode::InvalidEscape => "invalid escape",
+            ErrorCode::InvalidUnicodeCodePoint => "invalid unicode code point",
+            ErrorCode::LoneLeadingSurrogateInHexEscape => {
+                "lone leading surrogate in \\u escape"
+            }
+            ErrorCode::UnexpectedEndOfHexEscape => "unexpected end of hex escape",
+            ErrorCode::TrailingCharacters => "trailing characters",
+        };
+        write!(f, "{}", msg)
+    }
+}
+
+/// An error produced while parsing a JSON document, with the line and
+/// column at which it occurred (both 1-indexed).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParserError {
+    pub code: ErrorCode,
+    pub line: usize,
+    pub col: usize,
+}
+
+impl Display for ParserError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at line {} column {}", self.code, self.line, self.col)
+    }
+}
+
+impl std::error::Error for ParserError {}
+
+/// A character-driven JSON parser.
+///
+/// Walks the input one character at a time, tracking line and column so
+/// that `ParserError`s can point at the exact location of the problem.
+pub struct Parser<T: Iterator<Item = char>> {
+    chars: Peekable<T>,
+    ch: Option<char>,
+    line: usize,
+    col: usize,
+}
+
+impl<T: Iterator<Item = char>> Parser<T> {
+    pub fn new(src: T) -> Self {
+        let mut parser = Parser {
+            chars: src.peekable(),
+            ch: None,
+            line: 1,
+            col: 0,
+        };
+        parser.bump();
+        parser
+    }
+
+    /// Parses a single JSON value, then ensures only trailing whitespace
+    /// remains in the input.
+    pub fn parse(&mut self) -> Result<Json, ParserError> {
+        self.skip_whitespace();
+        let value = self.parse_value()?;
+        self.skip_whitespace();
+        if self.ch.is_some() {
+            return self.error(ErrorCode::TrailingCharacters);
+        }
+        Ok(value)
+    }
+
+    fn bump(&mut self) {
+        self.ch = self.chars.next();
+        match self.ch {
+            Some('\n') => {
+                self.line += 1;
+                self.col = 0;
+            }
+            Some(_) => self.col += 1,
+            None => {}
+        }
+    }
+
+    fn error<U>(&self, code: ErrorCode) -> Result<U, ParserError> {
+        Err(ParserError {
+            code,
+            line: self.line,
+            col: self.col,
+        })
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.ch, Some(c) if c.is_whitespace()) {
+            self.bump();
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<Json, ParserError> {
+        match self.ch {
+            None => self.error(ErrorCode::EOFWhileParsingValue),
+            Some('n') => self.parse_ident("null", Json::Null),
+            Some('t') => self.parse_ident("true", Json::Boolean(true)),
+            Some('f') => self.parse_ident("false", Json::Boolean(false)),
+            Some('"') => self.parse_string().map(Json::String),
+            Some('[') => self.parse_array(),
+            Some('{') => self.parse_object(),
+            Some(c) if c == '-' || c.is_ascii_digit() => self.parse_number(),
+            Some(_) => self.error(ErrorCode::InvalidSyntax),
+        }
+    }
+
+    fn parse_ident(&mut self, ident: &str, value: Json) -> Result<Json, ParserError> {
+        for expected in ident.chars() {
+            if self.ch != Some(expected) {
+                return self.error(ErrorCode::InvalidSyntax);
+            }
+            self.bump();
+        }
+        Ok(value)
+    }
+
+    fn parse_number(&mut self) -> Result<Json, ParserError> {
+        let mut buf = String::new();
+        if self.ch == Some('-') {
+            buf.push('-');
+            self.bump();
+        }
+        while matches!(self.ch, Some(c) if c.is_ascii_digit()) {
+            buf.push(self.ch.unwrap());
+            self.bump();
+        }
+        if self.ch == Some('.') {
+            buf.push('.');
+            self.bump();
+            while matches!(self.ch, Some(c) if c.is_ascii_digit()) {
+                buf.push(self.ch.unwrap());
+                self.bump();
+            }
+        }
+        if matches!(self.ch, Some('e') | Some('E')) {
+            buf.push(self.ch.unwrap());
+            self.bump();
+            if matches!(self.ch, Some('+') | Some('-')) {
+                buf.push(self.ch.unwrap());
+                self.bump();
+            }
+            while matches!(self.ch, Some(c) if c.is_ascii_digit()) {
+                buf.push(self.ch.unwrap());
+                self.bump();
+            }
+        }
+        buf.parse::<f64>()
+            .map(Json::Number)
+            .or_else(|_| self.error(ErrorCode::InvalidNumber))
+    }
+
+    fn parse_string(&mut self) -> Result<String, ParserError> {
+        // Opening quote.
+        self.bump();
+        let mut result = String::new();
+        loop {
+            match self.ch {
+                None => return self.error(ErrorCode::EOFWhileParsingString),
+                Some('"') => {
+                    self.bump();
+                    return Ok(result);
+                }
+                Some('\\') => {
+                    self.bump();
+                    match self.ch {
+                        None => return self.error(ErrorCode::EOFWhileParsingString),
+                        Some('"') => {
+                            result.push('"');
+                            self.bump();
+                        }
+                        Some('\\') => {
+                            result.push('\\');
+                            self.bump();
+                        }
+                        Some('/') => {
+                            result.push('/');
+                            self.bump();
+                        }
+                        Some('n') => {
+                            result.push('\n');
+                            self.bump();
+                        }
+                        Some('r') => {
+                            result.push('\r');
+                            self.bump();
+                        }
+                        Some('t') => {
+                            result.push('\t');
+                            self.bump();
+                        }
+                        Some('b') => {
+                            result.push('\u{8}');
+                            self.bump();
+                        }
+                        Some('f') => {
+                            result.push('\u{c}');
+                            self.bump();
+                        }
+                        Some('u') => {
+                            self.bump();
+                            let c = self.parse_unicode_escape()?;
+                            result.push(c);
+                        }
+                        Some(_) => return self.error(ErrorCode::InvalidEscape),
+                    }
+                }
+                Some(c) => {
+                    result.push(c);
+                    self.bump();
+                }
+            }
+        }
+    }
+
+    /// Parses a `\uXXXX` escape (the leading `\u` has already been
+    /// consumed), combining a surrogate pair into a single `char` when
+    /// present.
+    fn parse_unicode_escape(&mut self) -> Result<char, ParserError> {
+        let high = self.parse_hex4()?;
+
+        if (0xD800..=0xDBFF).contains(&high) {
+            if self.ch != Some('\\') {
+                return self.error(ErrorCode::UnexpectedEndOfHexEscape);
+            }
+            self.bump();
+            if self.ch != Some('u') {
+                return self.error(ErrorCode::UnexpectedEndOfHexEscape);
+            }
+            self.bump();
+            let low = self.parse_hex4()?;
+            if !(0xDC00..=0xDFFF).contains(&low) {
+                return self.error(ErrorCode::LoneLeadingSurrogateInHexEscape);
+            }
+            let combined = 0x10000 + (((high - 0xD800) as u32) << 10) + (low - 0xDC00) as u32;
+            char::from_u32(combined).ok_or(ParserError {
+                code: ErrorCode::InvalidUnicodeCodePoint,
+                line: self.line,
+                col: self.col,
+            })
+        } else if (0xDC00..=0xDFFF).contains(&high) {
+            self.error(ErrorCode::LoneLeadingSurrogateInHexEscape)
+        } else {
+            char::from_u32(high as u32).ok_or(ParserError {
+                code: ErrorCode::InvalidUnicodeCodePoint,
+                line: self.line,
+                col: self.col,
+            })
+        }
+    }
+
+    fn parse_hex4(&mut self) -> Result<u16, ParserError> {
+        let mut value: u16 = 0;
+        for _ in 0..4 {
+            let digit = match self.ch {
+                Some(c) => c.to_digit(16),
+                None => None,
+            };
+            let digit = match digit {
+                Some(d) => d,
+                None => return self.error(ErrorCode::UnexpectedEndOfHexEscape),
+            };
+            value = value * 16 + digit as u16;
+            self.bump();
+        }
+        Ok(value)
+    }
+
+    fn parse_array(&mut self) -> Result<Json, ParserError> {
+        self.bump();
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.ch == Some(']') {
+            self.bump();
+            return Ok(Json::Array(items));
+        }
+        loop {
+            self.skip_whitespace();
+            items.push(self.parse_value()?);
+            self.skip_whitespace();
+            match self.ch {
+                Some(',') => {
+                    self.bump();
+                }
+                Some(']') => {
+                    self.bump();
+                    return Ok(Json::Array(items));
+                }
+                None => return self.error(ErrorCode::EOFWhileParsingArray),
+                Some(_) => return self.error(ErrorCode::ExpectedListCommaOrEnd),
+            }
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<Json, ParserError> {
+        self.bump();
+        let mut map = BTreeMap::new();
+        self.skip_whitespace();
+        if self.ch == Some('}') {
+            self.bump();
+            return Ok(Json::Object(map));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.ch != Some('"') {
+                return self.error(ErrorCode::KeyMustBeAString);
+            }
+            let key = self.parse_string()?;
+            self.skip_whitespace();
+            if self.ch != Some(':') {
+                return self.error(ErrorCode::ExpectedColon);
+            }
+            self.bump();
+            self.skip_whitespace();
+            let value = self.parse_value()?;
+            map.insert(key, value);
+            self.skip_whitespace();
+            match self.ch {
+                Some(',') => {
+                    self.bump();
+                }
+                Some('}') => {
+                    self.bump();
+                    return Ok(Json::Object(map));
+                }
+                None => return self.error(ErrorCode::EOFWhileParsingObject),
+                Some(_) => return self.error(ErrorCode::ExpectedObjectCommaOrEnd),
+            }
+        }
+    }
+}
+
+/// Parses a complete `Json` value from a string.
+pub fn from_str(input: &str) -> Result<Json, ParserError> {
+    Parser::new(input.chars()).parse()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_primitives() {
+        assert_eq!(from_str("null").unwrap(), Json::Null);
+        assert_eq!(from_str("true").unwrap(), Json::Boolean(true));
+        assert_eq!(from_str("false").unwrap(), Json::Boolean(false));
+        assert_eq!(from_str("42").unwrap(), Json::Number(42.0));
+        assert_eq!(from_str("-3.5").unwrap(), Json::Number(-3.5));
+        assert_eq!(from_str("1e2").unwrap(), Json::Number(100.0));
+        assert_eq!(from_str("\"hi\"").unwrap(), Json::String("hi".to_string()));
+    }
+
+    #[test]
+    fn parses_array_and_object() {
+        assert_eq!(
+            from_str("[1, 2, 3]").unwrap(),
+            Json::Array(vec![Json::Number(1.0), Json::Number(2.0), Json::Number(3.0)])
+        );
+
+        let mut map = BTreeMap::new();
+        map.insert("a".to_string(), Json::Number(1.0));
+        map.insert("b".to_string(), Json::Boolean(true));
+        assert_eq!(from_str(r#"{"a": 1, "b": true}"#).unwrap(), Json::Object(map));
+    }
+
+    #[test]
+    fn parses_nested_document() {
+        let doc = r#"{
+            "name": "ferris",
+            "addresses": [{"city": "NYC", "zip": null}],
+            "active": true
+        }"#;
+        let parsed = from_str(doc).unwrap();
+        let Json::Object(map) = parsed else {
+            panic!("expected an object");
+        };
+        assert_eq!(map.get("name"), Some(&Json::String("ferris".to_string())));
+        assert_eq!(map.get("active"), Some(&Json::Boolean(true)));
+    }
+
+    #[test]
+    fn ignores_surrounding_whitespace() {
+        assert_eq!(from_str("  \n  42  \n").unwrap(), Json::Number(42.0));
+    }
+
+    #[test]
+    fn skips_whitespace_around_colon_and_comma() {
+        assert_eq!(
+            from_str("{ \"a\" :\n1 ,\n\"b\" : 2 }").unwrap(),
+            from_str(r#"{"a": 1, "b": 2}"#).unwrap()
+        );
+    }
+
+    #[test]
+    fn reports_eof_while_parsing_object() {
+        let err = from_str(r#"{"a": 1"#).unwrap_err();
+        assert_eq!(err.code, ErrorCode::EOFWhileParsingObject);
+    }
+
+    #[test]
+    fn reports_key_must_be_a_string() {
+        let err = from_str("{a: 1}").unwrap_err();
+        assert_eq!(err.code, ErrorCode::KeyMustBeAString);
+        assert_eq!(err.line, 1);
+        assert_eq!(err.col, 2);
+    }
+
+    #[test]
+    fn reports_expected_colon() {
+        let err = from_str(r#"{"a" 1}"#).unwrap_err();
+        assert_eq!(err.code, ErrorCode::ExpectedColon);
+        assert_eq!(err.line, 1);
+        assert_eq!(err.col, 6);
+    }
+
+    #[test]
+    fn reports_trailing_characters() {
+        let err = from_str("null null").unwrap_err();
+        assert_eq!(err.code, ErrorCode::TrailingCharacters);
+        assert_eq!(err.line, 1);
+        assert_eq!(err.col, 6);
+    }
+
+    #[test]
+    fn reports_eof_while_parsing_array() {
+        let err = from_str("[1, 2").unwrap_err();
+        assert_eq!(err.code, ErrorCode::EOFWhileParsingArray);
+    }
+
+    #[test]
+    fn reports_eof_while_parsing_string() {
+        let err = from_str("\"unterminated").unwrap_err();
+        assert_eq!(err.code, ErrorCode::EOFWhileParsingString);
+    }
+
+    #[test]
+    fn reports_invalid_syntax_for_bad_literal() {
+        let err = from_str("nul").unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidSyntax);
+    }
+
+    #[test]
+    fn tracks_line_and_column_across_newlines() {
+        let err = from_str("{\n  \"a\": 1,\n  b: 2\n}").unwrap_err();
+        assert_eq!(err.code, ErrorCode::KeyMustBeAString);
+        assert_eq!(err.line, 3);
+        assert_eq!(err.col, 3);
+    }
+}
@@ -0,0 +1,364 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{self, Display};
+
+use crate::Json;
+
+/// Converts a Rust value into a `Json` tree.
+pub trait ToJson {
+    fn to_json(&self) -> Json;
+}
+
+/// The inverse of `ToJson`: reconstructs a typed Rust value from a
+/// parsed `Json` tree.
+pub trait FromJson: Sized {
+    fn from_json(json: &Json) -> Result<Self, FromJsonError>;
+}
+
+/// The reason a `Json` tree could not be reconstructed into the
+/// requested type.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FromJsonError {
+    /// The `Json` value's shape didn't match what was expected, e.g. a
+    /// `Number` where a `String` was required.
+    TypeMismatch { expected: &'static str, found: Json },
+}
+
+impl Display for FromJsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FromJsonError::TypeMismatch { expected, found } => {
+                write!(f, "expected {}, found {:?}", expected, found)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FromJsonError {}
+
+macro_rules! impl_int {
+    ($($ty:ty),*) => {
+        $(
+            impl ToJson for $ty {
+                fn to_json(&self) -> Json {
+                    Json::Number(*self as f64)
+                }
+            }
+
+            impl FromJson for $ty {
+                fn from_json(json: &Json) -> Result<Self, FromJsonError> {
+                    match json {
+                        Json::Number(n)
+                            if n.is_finite()
+                                && n.fract() == 0.0
+                                && *n >= <$ty>::MIN as f64
+                                && *n <= <$ty>::MAX as f64 =>
+                        {
+                            Ok(*n as $ty)
+                        }
+                        other => Err(FromJsonError::TypeMismatch {
+                            expected: stringify!($ty),
+                            found: other.clone(),
+                        }),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_int!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+macro_rules! impl_float {
+    ($($ty:ty),*) => {
+        $(
+            impl ToJson for $ty {
+                fn to_json(&self) -> Json {
+                    // NaN and the infinities have no JSON representation, so
+                    // fall back to Null rather than emitting output that
+                    // wouldn't parse back (e.g. a bare `NaN` literal).
+                    if self.is_finite() {
+                        Json::Number(*self as f64)
+                    } else {
+                        Json::Null
+                    }
+                }
+            }
+
+            impl FromJson for $ty {
+                fn from_json(json: &Json) -> Result<Self, FromJsonError> {
+                    match json {
+                        Json::Number(n) => Ok(*n as $ty),
+                        other => Err(FromJsonError::TypeMismatch {
+                            expected: stringify!($ty),
+                            found: other.clone(),
+                        }),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_float!(f32, f64);
+
+impl ToJson for bool {
+    fn to_json(&self) -> Json {
+        Json::Boolean(*self)
+    }
+}
+
+impl FromJson for bool {
+    fn from_json(json: &Json) -> Result<Self, FromJsonError> {
+        match json {
+            Json::Boolean(b) => Ok(*b),
+            other => Err(FromJsonError::TypeMismatch {
+                expected: "bool",
+                found: other.clone(),
+            }),
+        }
+    }
+}
+
+impl ToJson for String {
+    fn to_json(&self) -> Json {
+        Json::String(self.clone())
+    }
+}
+
+impl FromJson for String {
+    fn from_json(json: &Json) -> Result<Self, FromJsonError> {
+        match json {
+            Json::String(s) => Ok(s.clone()),
+            other => Err(FromJsonError::TypeMismatch {
+                expected: "String",
+                found: other.clone(),
+            }),
+        }
+    }
+}
+
+impl ToJson for str {
+    fn to_json(&self) -> Json {
+        Json::String(self.to_string())
+    }
+}
+
+impl<T: ToJson> ToJson for Option<T> {
+    fn to_json(&self) -> Json {
+        match self {
+            Some(value) => value.to_json(),
+            None => Json::Null,
+        }
+    }
+}
+
+impl<T: FromJson> FromJson for Option<T> {
+    fn from_json(json: &Json) -> Result<Self, FromJsonError> {
+        match json {
+            Json::Null => Ok(None),
+            other => T::from_json(other).map(Some),
+        }
+    }
+}
+
+impl<T: ToJson> ToJson for Vec<T> {
+    fn to_json(&self) -> Json {
+        Json::Array(self.iter().map(ToJson::to_json).collect())
+    }
+}
+
+impl<T: FromJson> FromJson for Vec<T> {
+    fn from_json(json: &Json) -> Result<Self, FromJsonError> {
+        match json {
+            Json::Array(items) => items.iter().map(T::from_json).collect(),
+            other => Err(FromJsonError::TypeMismatch {
+                expected: "array",
+                found: other.clone(),
+            }),
+        }
+    }
+}
+
+impl<T: ToJson> ToJson for BTreeMap<String, T> {
+    fn to_json(&self) -> Json {
+        Json::Object(self.iter().map(|(k, v)| (k.clone(), v.to_json())).collect())
+    }
+}
+
+impl<T: FromJson> FromJson for BTreeMap<String, T> {
+    fn from_json(json: &Json) -> Result<Self, FromJsonError> {
+        match json {
+            Json::Object(map) => map
+                .iter()
+                .map(|(k, v)| T::from_json(v).map(|v| (k.clone(), v)))
+                .collect(),
+            other => Err(FromJsonError::TypeMismatch {
+                expected: "object",
+                found: other.clone(),
+            }),
+        }
+    }
+}
+
+impl<T: ToJson> ToJson for HashMap<String, T> {
+    fn to_json(&self) -> Json {
+        Json::Object(self.iter().map(|(k, v)| (k.clone(), v.to_json())).collect())
+    }
+}
+
+impl<T: FromJson> FromJson for HashMap<String, T> {
+    fn from_json(json: &Json) -> Result<Self, FromJsonError> {
+        match json {
+            Json::Object(map) => map
+                .iter()
+                .map(|(k, v)| T::from_json(v).map(|v| (k.clone(), v)))
+                .collect(),
+            other => Err(FromJsonError::TypeMismatch {
+                expected: "object",
+                found: other.clone(),
+            }),
+        }
+    }
+}
+
+macro_rules! impl_tuple {
+    ($($name:ident : $idx:tt),+) => {
+        impl<$($name: ToJson),+> ToJson for ($($name,)+) {
+            fn to_json(&self) -> Json {
+                Json::Array(vec![$(self.$idx.to_json()),+])
+            }
+        }
+
+        impl<$($name: FromJson),+> FromJson for ($($name,)+) {
+            fn from_json(json: &Json) -> Result<Self, FromJsonError> {
+                match json {
+                    Json::Array(items) => {
+                        Ok(($($name::from_json(items.get($idx).ok_or_else(|| FromJsonError::TypeMismatch {
+                            expected: "array with enough elements",
+                            found: json.clone(),
+                        })?)?,)+))
+                    }
+                    other => Err(FromJsonError::TypeMismatch {
+                        expected: "array",
+                        found: other.clone(),
+                    }),
+                }
+            }
+        }
+    };
+}
+
+impl_tuple!(A: 0, B: 1);
+impl_tuple!(A: 0, B: 1, C: 2);
+impl_tuple!(A: 0, B: 1, C: 2, D: 3);
+
+impl ToJson for Json {
+    fn to_json(&self) -> Json {
+        self.clone()
+    }
+}
+
+impl FromJson for Json {
+    fn from_json(json: &Json) -> Result<Self, FromJsonError> {
+        Ok(json.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_numbers_and_bool() {
+        assert_eq!(i32::from_json(&42i32.to_json()).unwrap(), 42);
+        assert_eq!(u8::from_json(&7u8.to_json()).unwrap(), 7);
+        assert_eq!(f64::from_json(&1.5f64.to_json()).unwrap(), 1.5);
+        assert!(bool::from_json(&true.to_json()).unwrap());
+    }
+
+    #[test]
+    fn round_trips_string() {
+        let s = "hello".to_string();
+        assert_eq!(String::from_json(&s.to_json()).unwrap(), s);
+    }
+
+    #[test]
+    fn round_trips_option() {
+        let some: Option<i32> = Some(3);
+        let none: Option<i32> = None;
+        assert_eq!(Option::<i32>::from_json(&some.to_json()).unwrap(), some);
+        assert_eq!(Option::<i32>::from_json(&none.to_json()).unwrap(), none);
+    }
+
+    #[test]
+    fn round_trips_vec() {
+        let v = vec![1, 2, 3];
+        assert_eq!(Vec::<i32>::from_json(&v.to_json()).unwrap(), v);
+    }
+
+    #[test]
+    fn round_trips_btreemap() {
+        let mut map = BTreeMap::new();
+        map.insert("a".to_string(), 1);
+        map.insert("b".to_string(), 2);
+        assert_eq!(
+            BTreeMap::<String, i32>::from_json(&map.to_json()).unwrap(),
+            map
+        );
+    }
+
+    #[test]
+    fn round_trips_hashmap() {
+        let mut map = HashMap::new();
+        map.insert("a".to_string(), 1);
+        assert_eq!(HashMap::<String, i32>::from_json(&map.to_json()).unwrap(), map);
+    }
+
+    #[test]
+    fn round_trips_tuple() {
+        let t = (1i32, "two".to_string(), 3.0f64);
+        assert_eq!(
+            <(i32, String, f64)>::from_json(&t.to_json()).unwrap(),
+            t
+        );
+    }
+
+    #[test]
+    fn type_mismatch_error() {
+        let err = i32::from_json(&Json::String("not a number".to_string())).unwrap_err();
+        assert_eq!(
+            err,
+            FromJsonError::TypeMismatch {
+                expected: "i32",
+                found: Json::String("not a number".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn out_of_range_number_is_rejected_not_saturated() {
+        let err = u32::from_json(&Json::Number(-5.0)).unwrap_err();
+        assert_eq!(
+            err,
+            FromJsonError::TypeMismatch {
+                expected: "u32",
+                found: Json::Number(-5.0),
+            }
+        );
+    }
+
+    #[test]
+    fn fractional_number_is_rejected_for_integers() {
+        assert!(i32::from_json(&Json::Number(1.5)).is_err());
+    }
+
+    #[test]
+    fn non_finite_floats_encode_as_null() {
+        assert_eq!(f64::NAN.to_json(), Json::Null);
+        assert_eq!(f64::INFINITY.to_json(), Json::Null);
+        assert_eq!(f64::NEG_INFINITY.to_json(), Json::Null);
+
+        let encoded = f64::NAN.to_json().encode(2);
+        assert_eq!(crate::parser::from_str(&encoded).unwrap(), Json::Null);
+    }
+}